@@ -6,55 +6,84 @@ use crate::vm::{LinaValue, OpCode};
 
 type VarTable<'a> = HashMap<&'a str, usize>;
 
+// Operands are encoded as fixed-width little-endian u64/i64 so emitted
+// bytecode is portable across targets regardless of native usize/isize width.
+pub(crate) const ADDR_SIZE: usize = 8;
+pub(crate) const OFFSET_SIZE: usize = 8;
+
+#[derive(Debug)]
+struct LoopContext {
+    // positions of placeholder offsets pending backpatch once the target is known
+    break_jumps: Vec<usize>,
+    continue_jumps: Vec<usize>,
+}
+
 #[derive(Debug)]
 pub struct Compiler<'a> {
     pub bytecode: Vec<u8>,
     pub constants: Vec<LinaValue>,
     scopes: Vec<VarTable<'a>>,
+    loops: Vec<LoopContext>,
     vi: usize,
 }
 
+impl<'a> Default for Compiler<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> Compiler<'a> {
     pub fn new() -> Self {
         Self {
             bytecode: Vec::new(),
             constants: Vec::new(),
             scopes: vec![HashMap::new()],
+            loops: Vec::new(),
             vi: 0,
         }
     }
 
     fn op_const(&mut self, addr: usize) {
         self.bytecode.push(OpCode::Const as u8);
-        self.bytecode.extend(usize::to_ne_bytes(addr));
+        self.bytecode.extend((addr as u64).to_le_bytes());
     }
 
     fn op_store(&mut self, addr: usize) {
         self.bytecode.push(OpCode::Store as u8);
-        self.bytecode.extend(usize::to_ne_bytes(addr));
+        self.bytecode.extend((addr as u64).to_le_bytes());
     }
 
     fn op_load(&mut self, addr: usize) {
         self.bytecode.push(OpCode::Load as u8);
-        self.bytecode.extend(usize::to_ne_bytes(addr));
+        self.bytecode.extend((addr as u64).to_le_bytes());
     }
 
     fn push_offset(&mut self, offset: isize) {
         // include itself on push
-        const SIZE: isize = isize::BITS as isize / 8;
+        const SIZE: isize = OFFSET_SIZE as isize;
         let total = offset + (SIZE * offset.signum());
-        self.bytecode.extend(isize::to_ne_bytes(total));
+        self.bytecode.extend((total as i64).to_le_bytes());
     }
 
     fn insert_offset(&mut self, offset: isize, pos: usize) {
-        let value = isize::to_ne_bytes(offset);
-        self.bytecode[pos..pos + std::mem::size_of::<isize>()].copy_from_slice(&value);
+        let value = (offset as i64).to_le_bytes();
+        self.bytecode[pos..pos + OFFSET_SIZE].copy_from_slice(&value);
     }
 
     fn op(&mut self, op: OpCode) {
         self.bytecode.push(op as u8);
     }
 
+    // backpatches every recorded placeholder offset to land at `target`
+    fn patch_jumps(&mut self, positions: Vec<usize>, target: usize) {
+        for pos in positions {
+            let start = pos + OFFSET_SIZE;
+            let offset = target as isize - start as isize;
+            self.insert_offset(offset, pos);
+        }
+    }
+
     fn enter_scope(&mut self) {
         self.scopes.push(HashMap::new());
     }
@@ -86,6 +115,7 @@ impl<'a> Compiler<'a> {
     }
 
     pub fn compile(&mut self, program: &'a Program<'a>) {
+        check_loop_usage(&program.block, 0);
         self.compile_block(&program.block);
         self.bytecode.push(OpCode::Halt as u8);
     }
@@ -113,19 +143,40 @@ impl<'a> Compiler<'a> {
             SyntaxTree::SeStmt {
                 exp: expr,
                 blk: block,
+                sen: else_block,
             } => {
                 self.compile_expr(expr);
                 self.op(OpCode::JmpF); // jump if expression is false
 
-                let jmp_offset_pos = self.bytecode.len(); // offset pos
+                let jmpf_offset_pos = self.bytecode.len(); // offset pos
                 self.push_offset(0); // placeholder for jump offset
 
                 let start = self.bytecode.len(); // start of block
                 self.compile_block(block);
-                let end = self.bytecode.len(); // end of block
 
-                let jmp_offset = (end - start) as isize; // length of block
-                self.insert_offset(jmp_offset, jmp_offset_pos); // jump over the block
+                match else_block {
+                    None => {
+                        let end = self.bytecode.len(); // end of block
+                        let jmp_offset = (end - start) as isize; // length of block
+                        self.insert_offset(jmp_offset, jmpf_offset_pos); // jump over the block
+                    }
+                    Some(else_block) => {
+                        self.op(OpCode::Jmp); // skip the else block once the then-block ran
+
+                        let jmp_offset_pos = self.bytecode.len();
+                        self.push_offset(0); // placeholder for jump offset
+
+                        let else_start = self.bytecode.len(); // start of else block
+                        let jmpf_offset = (else_start - start) as isize;
+                        self.insert_offset(jmpf_offset, jmpf_offset_pos); // land on the else block
+
+                        self.compile_block(else_block);
+                        let end = self.bytecode.len(); // end of else block
+
+                        let jmp_offset = (end - else_start) as isize; // length of else block
+                        self.insert_offset(jmp_offset, jmp_offset_pos); // jump over the else block
+                    }
+                }
             }
             SyntaxTree::EnquantoStmt {
                 exp: expr,
@@ -139,8 +190,18 @@ impl<'a> Compiler<'a> {
                 let jmpf_offset_pos = self.bytecode.len();
                 self.push_offset(0); // placeholder for the jump out
 
+                self.loops.push(LoopContext {
+                    break_jumps: Vec::new(),
+                    continue_jumps: Vec::new(),
+                });
+
                 let block_start = self.bytecode.len();
                 self.compile_block(block);
+
+                // continua jumps back to the condition re-evaluation point
+                let ctx = self.loops.pop().unwrap();
+                self.patch_jumps(ctx.continue_jumps, start);
+
                 self.op(OpCode::Jmp);
 
                 let end = self.bytecode.len(); //  end while expression
@@ -150,6 +211,8 @@ impl<'a> Compiler<'a> {
                 let end = self.bytecode.len();
                 let jmp_offset = (end - block_start) as isize; // this will skip the block and jmp
                 self.insert_offset(jmp_offset, jmpf_offset_pos);
+
+                self.patch_jumps(ctx.break_jumps, end);
             }
             SyntaxTree::ParaStmt {
                 idt: ident,
@@ -167,9 +230,19 @@ impl<'a> Compiler<'a> {
                 let jmp_offset_pos = self.bytecode.len();
                 self.push_offset(0);
 
+                self.loops.push(LoopContext {
+                    break_jumps: Vec::new(),
+                    continue_jumps: Vec::new(),
+                });
+
                 let block_start = self.bytecode.len();
                 self.compile_block(block);
 
+                // continua jumps to the increment step, not back to the condition
+                let incr_start = self.bytecode.len();
+                let ctx = self.loops.pop().unwrap();
+                self.patch_jumps(ctx.continue_jumps, incr_start);
+
                 self.op_load(addr);
                 self.compile_literal(&Literal::Decimal(1.0));
                 self.op(OpCode::Add);
@@ -183,7 +256,23 @@ impl<'a> Compiler<'a> {
 
                 let end = self.bytecode.len();
                 let jmp_offset = (end - block_start) as isize;
-                self.insert_offset(jmp_offset, jmp_offset_pos)
+                self.insert_offset(jmp_offset, jmp_offset_pos);
+
+                self.patch_jumps(ctx.break_jumps, end);
+            }
+            // `check_loop_usage` already rejected a stray 'interrompa'/'continua' before
+            // compilation started, so a loop context is guaranteed to be open here.
+            SyntaxTree::Break => {
+                self.op(OpCode::Jmp);
+                let pos = self.bytecode.len();
+                self.push_offset(0);
+                self.loops.last_mut().unwrap().break_jumps.push(pos);
+            }
+            SyntaxTree::Continue => {
+                self.op(OpCode::Jmp);
+                let pos = self.bytecode.len();
+                self.push_offset(0);
+                self.loops.last_mut().unwrap().continue_jumps.push(pos);
             }
             SyntaxTree::Expr(expr) => {
                 self.compile_expr(expr);
@@ -224,7 +313,52 @@ impl<'a> Compiler<'a> {
                 let addr = self.get_var(idt);
                 self.op_load(addr);
             }
-            Expression::BinOp { ope, lhs, rhs, typ } => {
+            Expression::BinOp {
+                ope: Operador::E,
+                lhs,
+                rhs,
+                typ: _,
+            } => {
+                // short-circuit: if lhs is false, leave it on the stack and skip rhs
+                self.compile_expr(lhs);
+                self.op(OpCode::Dup);
+                self.op(OpCode::JmpF);
+
+                let jmpf_offset_pos = self.bytecode.len();
+                self.push_offset(0);
+
+                let start = self.bytecode.len();
+                self.op(OpCode::Pop);
+                self.compile_expr(rhs);
+                let end = self.bytecode.len();
+
+                let jmpf_offset = (end - start) as isize;
+                self.insert_offset(jmpf_offset, jmpf_offset_pos);
+            }
+            Expression::BinOp {
+                ope: Operador::Ou,
+                lhs,
+                rhs,
+                typ: _,
+            } => {
+                // short-circuit: if lhs is true, leave it on the stack and skip rhs,
+                // mirrored from the `E` arm above via `JmpT` (pop-and-jump-if-true)
+                self.compile_expr(lhs);
+                self.op(OpCode::Dup);
+                self.op(OpCode::JmpT);
+
+                let jmpt_offset_pos = self.bytecode.len();
+                self.push_offset(0);
+
+                let start = self.bytecode.len();
+                self.op(OpCode::Pop);
+                self.compile_expr(rhs);
+                let end = self.bytecode.len();
+
+                let jmpt_offset = (end - start) as isize;
+                self.insert_offset(jmpt_offset, jmpt_offset_pos);
+            }
+            Expression::BinOp { ope, lhs, rhs, typ: _ } => {
                 // Atrib (:=) does not need a left hand side
                 if *ope != Operador::Atrib {
                     self.compile_expr(lhs);
@@ -239,18 +373,18 @@ impl<'a> Compiler<'a> {
                     Operador::Igual => self.op(OpCode::Eq),
                     Operador::Diferente => self.op(OpCode::NE),
 
-                    Operador::E => self.op(OpCode::And),
-                    Operador::Ou => self.op(OpCode::Or),
-
                     Operador::Adic | Operador::AdicAtrib => self.op(OpCode::Add),
                     Operador::Subt | Operador::SubtAtrib => self.op(OpCode::Sub),
                     Operador::Mult | Operador::MultAtrib => self.op(OpCode::Mul),
                     Operador::Div | Operador::DivAtrib => self.op(OpCode::Div),
 
                     Operador::Resto | Operador::RestoAtrib => self.op(OpCode::Rem),
-                    Operador::Exp | Operador::ExpAtrib => todo!(),
+                    Operador::Exp | Operador::ExpAtrib => self.op(OpCode::Pow),
 
                     Operador::Atrib => {}
+
+                    // handled by the dedicated short-circuiting arms above
+                    Operador::E | Operador::Ou => unreachable!(),
                 };
 
                 if ope.is_atrib() {
@@ -262,6 +396,34 @@ impl<'a> Compiler<'a> {
                     self.op_store(addr);
                 }
             }
+            Expression::Conditional {
+                cond,
+                then_branch,
+                else_branch,
+            } => {
+                self.compile_expr(cond);
+                self.op(OpCode::JmpF);
+
+                let jmpf_offset_pos = self.bytecode.len();
+                self.push_offset(0);
+
+                let then_start = self.bytecode.len();
+                self.compile_expr(then_branch);
+                self.op(OpCode::Jmp);
+
+                let jmp_offset_pos = self.bytecode.len();
+                self.push_offset(0);
+
+                let else_start = self.bytecode.len();
+                let jmpf_offset = (else_start - then_start) as isize;
+                self.insert_offset(jmpf_offset, jmpf_offset_pos);
+
+                self.compile_expr(else_branch);
+                let end = self.bytecode.len();
+
+                let jmp_offset = (end - else_start) as isize;
+                self.insert_offset(jmp_offset, jmp_offset_pos);
+            }
             Expression::Cast(exp, typ) => {
                 self.compile_expr(exp);
                 match typ {
@@ -280,3 +442,256 @@ pub fn compile<'a>(program: &'a Program<'a>) -> Compiler<'a> {
     compiler.compile(program);
     compiler
 }
+
+/// Walks the whole program before any bytecode is emitted and rejects an
+/// 'interrompa'/'continua' that is not nested inside a loop, so the error is
+/// reported at compile time instead of only surfacing once codegen reaches it.
+fn check_loop_usage(block: &Block, loop_depth: usize) {
+    for stmt in block.iter_stmts() {
+        match stmt {
+            SyntaxTree::Break => {
+                if loop_depth == 0 {
+                    panic!("ERRO: 'interrompa' usado fora de um laço");
+                }
+            }
+            SyntaxTree::Continue => {
+                if loop_depth == 0 {
+                    panic!("ERRO: 'continua' usado fora de um laço");
+                }
+            }
+            SyntaxTree::SeStmt { blk, sen, .. } => {
+                check_loop_usage(blk, loop_depth);
+                if let Some(sen) = sen {
+                    check_loop_usage(sen, loop_depth);
+                }
+            }
+            SyntaxTree::EnquantoStmt { blk, .. } | SyntaxTree::ParaStmt { blk, .. } => {
+                check_loop_usage(blk, loop_depth + 1);
+            }
+            SyntaxTree::Assign { .. } | SyntaxTree::Expr(_) | SyntaxTree::Print(_) => {}
+        }
+    }
+}
+
+const MODULE_MAGIC: &[u8; 4] = b"LINA";
+const MODULE_VERSION: u16 = 1;
+
+const TAG_INT32: u8 = 0;
+const TAG_FLOAT32: u8 = 1;
+const TAG_BOOLEAN: u8 = 2;
+const TAG_STRING: u8 = 3;
+
+impl<'a> Compiler<'a> {
+    /// Serializes the compiled bytecode and constant pool into a portable,
+    /// versioned module so it can be saved and later loaded by the VM without
+    /// re-parsing the source.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MODULE_MAGIC);
+        out.extend_from_slice(&MODULE_VERSION.to_le_bytes());
+
+        out.extend_from_slice(&(self.constants.len() as u32).to_le_bytes());
+        for value in &self.constants {
+            match value {
+                LinaValue::Int32(n) => {
+                    out.push(TAG_INT32);
+                    out.extend_from_slice(&n.to_le_bytes());
+                }
+                LinaValue::Float32(n) => {
+                    out.push(TAG_FLOAT32);
+                    out.extend_from_slice(&n.to_le_bytes());
+                }
+                LinaValue::Boolean(b) => {
+                    out.push(TAG_BOOLEAN);
+                    out.push(*b as u8);
+                }
+                LinaValue::String(s) => {
+                    out.push(TAG_STRING);
+                    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                    out.extend_from_slice(s.as_bytes());
+                }
+            }
+        }
+
+        out.extend_from_slice(&(self.bytecode.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.bytecode);
+
+        out
+    }
+}
+
+/// Loads a module produced by [`Compiler::serialize`], returning the
+/// `(bytecode, constants)` pair the VM consumes. Rejects unknown
+/// magic/version headers and out-of-range constant indices.
+pub fn load_module(bytes: &[u8]) -> Result<(Vec<u8>, Vec<LinaValue>), String> {
+    let mut pos = 0;
+
+    if bytes.len() < MODULE_MAGIC.len() || &bytes[..MODULE_MAGIC.len()] != MODULE_MAGIC {
+        return Err("ERRO: cabeçalho de módulo inválido".into());
+    }
+    pos += MODULE_MAGIC.len();
+
+    let version = u16::from_le_bytes(read_bytes(bytes, &mut pos, 2)?.try_into().unwrap());
+    if version != MODULE_VERSION {
+        return Err(format!("ERRO: versão de módulo não suportada: {version}"));
+    }
+
+    let constants_len = read_u32(bytes, &mut pos)? as usize;
+    let mut constants = Vec::with_capacity(constants_len);
+    for _ in 0..constants_len {
+        let tag = *read_bytes(bytes, &mut pos, 1)?.first().unwrap();
+        let value = match tag {
+            TAG_INT32 => {
+                LinaValue::Int32(i32::from_le_bytes(read_bytes(bytes, &mut pos, 4)?.try_into().unwrap()))
+            }
+            TAG_FLOAT32 => {
+                LinaValue::Float32(f32::from_le_bytes(read_bytes(bytes, &mut pos, 4)?.try_into().unwrap()))
+            }
+            TAG_BOOLEAN => LinaValue::Boolean(read_bytes(bytes, &mut pos, 1)?[0] != 0),
+            TAG_STRING => {
+                let len = read_u32(bytes, &mut pos)? as usize;
+                let text = read_bytes(bytes, &mut pos, len)?;
+                let text = String::from_utf8(text.to_vec())
+                    .map_err(|_| "ERRO: texto de constante não é UTF-8 válido".to_string())?;
+                LinaValue::String(text)
+            }
+            _ => return Err(format!("ERRO: tag de constante desconhecida: {tag}")),
+        };
+        constants.push(value);
+    }
+
+    let bytecode_len = read_u32(bytes, &mut pos)? as usize;
+    let bytecode = read_bytes(bytes, &mut pos, bytecode_len)?.to_vec();
+
+    validate_constant_indices(&bytecode, constants.len())?;
+
+    Ok((bytecode, constants))
+}
+
+fn read_bytes<'b>(bytes: &'b [u8], pos: &mut usize, len: usize) -> Result<&'b [u8], String> {
+    let end = *pos + len;
+    if end > bytes.len() {
+        return Err("ERRO: módulo truncado".into());
+    }
+    let slice = &bytes[*pos..end];
+    *pos = end;
+    Ok(slice)
+}
+
+fn read_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, String> {
+    let slice = read_bytes(bytes, pos, 4)?;
+    Ok(u32::from_le_bytes(slice.try_into().unwrap()))
+}
+
+fn validate_constant_indices(bytecode: &[u8], constants_len: usize) -> Result<(), String> {
+    let mut ip = 0;
+    while ip < bytecode.len() {
+        let op = bytecode[ip];
+        ip += 1;
+
+        if op == OpCode::Const as u8 {
+            let addr = u64::from_le_bytes(bytecode[ip..ip + ADDR_SIZE].try_into().unwrap()) as usize;
+            if addr >= constants_len {
+                return Err(format!("ERRO: índice de constante fora do intervalo: {addr}"));
+            }
+            ip += ADDR_SIZE;
+        } else if op == OpCode::Store as u8 || op == OpCode::Load as u8 {
+            ip += ADDR_SIZE;
+        } else if op == OpCode::Jmp as u8 || op == OpCode::JmpF as u8 || op == OpCode::JmpT as u8 {
+            ip += OFFSET_SIZE;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_compiler() -> Compiler<'static> {
+        let mut compiler = Compiler::new();
+        compiler.compile_literal(&Literal::Inteiro(42));
+        compiler.compile_literal(&Literal::Texto("olá"));
+        compiler.op(OpCode::Write);
+        compiler.op(OpCode::Halt);
+        compiler
+    }
+
+    #[test]
+    fn serialize_then_load_round_trips() {
+        let compiler = sample_compiler();
+        let bytes = compiler.serialize();
+
+        let (bytecode, constants) = load_module(&bytes).unwrap();
+
+        assert_eq!(bytecode, compiler.bytecode);
+        assert_eq!(constants, compiler.constants);
+    }
+
+    #[test]
+    fn load_module_rejects_bad_magic() {
+        let mut bytes = sample_compiler().serialize();
+        bytes[0] = b'X';
+
+        assert!(load_module(&bytes).is_err());
+    }
+
+    #[test]
+    fn load_module_rejects_bad_version() {
+        let mut bytes = sample_compiler().serialize();
+        bytes[MODULE_MAGIC.len()..MODULE_MAGIC.len() + 2].copy_from_slice(&99u16.to_le_bytes());
+
+        assert!(load_module(&bytes).is_err());
+    }
+
+    #[test]
+    fn load_module_rejects_truncated_input() {
+        let bytes = sample_compiler().serialize();
+
+        assert!(load_module(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn load_module_rejects_out_of_range_constant_index() {
+        let compiler = sample_compiler();
+        let mut bytes = compiler.serialize();
+
+        // the bytecode blob is the tail of the module and starts with a `Const`;
+        // point its operand past the end of the two-entry constant pool
+        let operand_pos = bytes.len() - compiler.bytecode.len() + 1;
+        bytes[operand_pos..operand_pos + ADDR_SIZE].copy_from_slice(&99u64.to_le_bytes());
+
+        assert!(load_module(&bytes).unwrap_err().contains("fora do intervalo"));
+    }
+
+    #[test]
+    fn load_module_rejects_invalid_utf8_string_constant() {
+        let mut compiler = Compiler::new();
+        compiler.compile_literal(&Literal::Texto("x"));
+        let mut bytes = compiler.serialize();
+
+        // corrupt the one-byte string payload ("x"): magic + version + constants_len + tag + len-prefix
+        let string_payload_pos = MODULE_MAGIC.len() + 2 + 4 + 1 + 4;
+        bytes[string_payload_pos] = 0xFF;
+
+        assert!(load_module(&bytes).is_err());
+    }
+
+    #[test]
+    fn conditional_executes_only_the_taken_branch() {
+        let mut compiler = Compiler::new();
+        let expr = Expression::Conditional {
+            cond: Box::new(Expression::Literal(Literal::Booleano(true))),
+            then_branch: Box::new(Expression::Literal(Literal::Inteiro(1))),
+            else_branch: Box::new(Expression::Literal(Literal::Inteiro(2))),
+        };
+        compiler.compile_expr(&expr);
+        compiler.op(OpCode::Halt);
+
+        let mut vm = crate::vm::Vm::new(compiler.bytecode.clone(), compiler.constants.clone());
+        vm.run();
+
+        assert_eq!(vm.stack_top(), Some(&LinaValue::Int32(1)));
+    }
+}
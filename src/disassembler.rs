@@ -0,0 +1,117 @@
+use crate::compiler::{Compiler, ADDR_SIZE, OFFSET_SIZE};
+use crate::vm::OpCode;
+
+/// Walks the bytecode produced by a [`Compiler`] and returns a human-readable
+/// listing, one instruction per line, prefixed with its byte offset. Useful
+/// for inspecting generated code and spotting bugs in offset patching.
+pub fn disassemble(compiler: &Compiler) -> String {
+    let code = &compiler.bytecode;
+    let mut out = String::new();
+    let mut ip = 0;
+
+    while ip < code.len() {
+        let offset = ip;
+        let op = decode_op(code[ip]);
+        ip += 1;
+
+        match op {
+            OpCode::Const => {
+                let addr = read_addr(code, ip);
+                ip += ADDR_SIZE;
+                let value = &compiler.constants[addr];
+                out.push_str(&format!("{offset:04} CONST {addr}  ; {value:?}\n"));
+            }
+            OpCode::Store => {
+                let addr = read_addr(code, ip);
+                ip += ADDR_SIZE;
+                out.push_str(&format!("{offset:04} STORE {addr}\n"));
+            }
+            OpCode::Load => {
+                let addr = read_addr(code, ip);
+                ip += ADDR_SIZE;
+                out.push_str(&format!("{offset:04} LOAD {addr}\n"));
+            }
+            OpCode::Jmp | OpCode::JmpF | OpCode::JmpT => {
+                let rel = read_offset(code, ip);
+                ip += OFFSET_SIZE;
+                let target = (ip as isize + rel) as usize;
+                out.push_str(&format!("{offset:04} {op:?} {rel}  ; -> {target:04}\n"));
+            }
+            _ => out.push_str(&format!("{offset:04} {op:?}\n")),
+        }
+    }
+
+    out
+}
+
+fn decode_op(byte: u8) -> OpCode {
+    match byte {
+        b if b == OpCode::Const as u8 => OpCode::Const,
+        b if b == OpCode::Store as u8 => OpCode::Store,
+        b if b == OpCode::Load as u8 => OpCode::Load,
+        b if b == OpCode::Jmp as u8 => OpCode::Jmp,
+        b if b == OpCode::JmpF as u8 => OpCode::JmpF,
+        b if b == OpCode::JmpT as u8 => OpCode::JmpT,
+        b if b == OpCode::Add as u8 => OpCode::Add,
+        b if b == OpCode::Sub as u8 => OpCode::Sub,
+        b if b == OpCode::Mul as u8 => OpCode::Mul,
+        b if b == OpCode::Div as u8 => OpCode::Div,
+        b if b == OpCode::Rem as u8 => OpCode::Rem,
+        b if b == OpCode::Pow as u8 => OpCode::Pow,
+        b if b == OpCode::GT as u8 => OpCode::GT,
+        b if b == OpCode::LT as u8 => OpCode::LT,
+        b if b == OpCode::GE as u8 => OpCode::GE,
+        b if b == OpCode::LE as u8 => OpCode::LE,
+        b if b == OpCode::Eq as u8 => OpCode::Eq,
+        b if b == OpCode::NE as u8 => OpCode::NE,
+        b if b == OpCode::And as u8 => OpCode::And,
+        b if b == OpCode::Or as u8 => OpCode::Or,
+        b if b == OpCode::CastI as u8 => OpCode::CastI,
+        b if b == OpCode::CastF as u8 => OpCode::CastF,
+        b if b == OpCode::CastS as u8 => OpCode::CastS,
+        b if b == OpCode::Dup as u8 => OpCode::Dup,
+        b if b == OpCode::Pop as u8 => OpCode::Pop,
+        b if b == OpCode::Write as u8 => OpCode::Write,
+        b if b == OpCode::Halt as u8 => OpCode::Halt,
+        _ => panic!("ERRO: opcode desconhecido: {byte}"),
+    }
+}
+
+// Operands are fixed-width u64/i64 regardless of the host's native usize/isize
+// width, matching how the compiler encodes them.
+fn read_addr(code: &[u8], pos: usize) -> usize {
+    let bytes = code[pos..pos + ADDR_SIZE].try_into().unwrap();
+    u64::from_le_bytes(bytes) as usize
+}
+
+fn read_offset(code: &[u8], pos: usize) -> isize {
+    let bytes = code[pos..pos + OFFSET_SIZE].try_into().unwrap();
+    i64::from_le_bytes(bytes) as isize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::Compiler;
+    use crate::token::Literal;
+
+    #[test]
+    fn disassemble_labels_const_and_resolves_jump_targets() {
+        let mut compiler = Compiler::new();
+        compiler.compile_literal(&Literal::Booleano(true));
+
+        compiler.bytecode.push(OpCode::JmpF as u8);
+        let pos = compiler.bytecode.len();
+        compiler.bytecode.extend(0i64.to_le_bytes());
+        let target = compiler.bytecode.len() as isize;
+        compiler.bytecode[pos..pos + OFFSET_SIZE]
+            .copy_from_slice(&(target - pos as isize - OFFSET_SIZE as isize).to_le_bytes());
+        compiler.bytecode.push(OpCode::Halt as u8);
+
+        let listing = disassemble(&compiler);
+
+        assert!(listing.contains("CONST 0"));
+        assert!(listing.contains("0009 JmpF 0  ; -> 0018"));
+        assert!(listing.contains("Halt"));
+    }
+}
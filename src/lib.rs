@@ -0,0 +1,5 @@
+pub mod compiler;
+pub mod disassembler;
+pub mod syntax;
+pub mod token;
+pub mod vm;
@@ -0,0 +1,91 @@
+use crate::token::{Literal, Operador};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type {
+    Integer,
+    Real,
+    Text,
+    Boolean,
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Type::Integer => "inteiro",
+            Type::Real => "real",
+            Type::Text => "texto",
+            Type::Boolean => "booleano",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Expression<'a> {
+    Literal(Literal<'a>),
+    Identifier(&'a str, Type),
+    BinOp {
+        ope: Operador,
+        lhs: Box<Expression<'a>>,
+        rhs: Box<Expression<'a>>,
+        typ: Type,
+    },
+    Cast(Box<Expression<'a>>, Type),
+    /// A ternary `cond ? then_branch : else_branch`; both branches are
+    /// compiled so exactly one ever executes, leaving one value on the stack.
+    Conditional {
+        cond: Box<Expression<'a>>,
+        then_branch: Box<Expression<'a>>,
+        else_branch: Box<Expression<'a>>,
+    },
+}
+
+#[derive(Debug)]
+pub enum SyntaxTree<'a> {
+    Assign {
+        idt: &'a str,
+        exp: Expression<'a>,
+        pos: usize,
+        typ: Type,
+    },
+    SeStmt {
+        exp: Expression<'a>,
+        blk: Block<'a>,
+        /// The `senão <bloco>` clause, if the parser found one; `None` for a
+        /// plain `se` with no else branch.
+        sen: Option<Block<'a>>,
+    },
+    EnquantoStmt {
+        exp: Expression<'a>,
+        blk: Block<'a>,
+    },
+    ParaStmt {
+        idt: &'a str,
+        lmt: Literal<'a>,
+        blk: Block<'a>,
+    },
+    Break,
+    Continue,
+    Expr(Expression<'a>),
+    Print(Expression<'a>),
+}
+
+#[derive(Debug)]
+pub struct Block<'a> {
+    stmts: Vec<SyntaxTree<'a>>,
+}
+
+impl<'a> Block<'a> {
+    pub fn new(stmts: Vec<SyntaxTree<'a>>) -> Self {
+        Self { stmts }
+    }
+
+    pub fn iter_stmts(&self) -> impl Iterator<Item = &SyntaxTree<'a>> {
+        self.stmts.iter()
+    }
+}
+
+#[derive(Debug)]
+pub struct Program<'a> {
+    pub block: Block<'a>,
+}
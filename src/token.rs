@@ -0,0 +1,51 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operador {
+    MaiorQue,
+    MenorQue,
+    MaiorIgualQue,
+    MenorIgualQue,
+    Igual,
+    Diferente,
+
+    E,
+    Ou,
+
+    Adic,
+    AdicAtrib,
+    Subt,
+    SubtAtrib,
+    Mult,
+    MultAtrib,
+    Div,
+    DivAtrib,
+    Resto,
+    RestoAtrib,
+    Exp,
+    ExpAtrib,
+
+    Atrib,
+}
+
+impl Operador {
+    pub fn is_atrib(&self) -> bool {
+        matches!(
+            self,
+            Operador::AdicAtrib
+                | Operador::SubtAtrib
+                | Operador::MultAtrib
+                | Operador::DivAtrib
+                | Operador::RestoAtrib
+                | Operador::ExpAtrib
+                | Operador::Atrib
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Literal<'a> {
+    Decimal(f32),
+    Inteiro(i32),
+    Texto(&'a str),
+    Booleano(bool),
+    Nulo,
+}
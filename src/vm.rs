@@ -0,0 +1,350 @@
+use crate::compiler::{ADDR_SIZE, OFFSET_SIZE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OpCode {
+    Const,
+    Store,
+    Load,
+
+    Jmp,
+    JmpF,
+    JmpT,
+
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+
+    GT,
+    LT,
+    GE,
+    LE,
+    Eq,
+    NE,
+
+    And,
+    Or,
+
+    CastI,
+    CastF,
+    CastS,
+
+    Dup,
+    Pop,
+    Write,
+    Halt,
+}
+
+impl TryFrom<u8> for OpCode {
+    type Error = String;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        const TABLE: &[OpCode] = &[
+            OpCode::Const,
+            OpCode::Store,
+            OpCode::Load,
+            OpCode::Jmp,
+            OpCode::JmpF,
+            OpCode::JmpT,
+            OpCode::Add,
+            OpCode::Sub,
+            OpCode::Mul,
+            OpCode::Div,
+            OpCode::Rem,
+            OpCode::Pow,
+            OpCode::GT,
+            OpCode::LT,
+            OpCode::GE,
+            OpCode::LE,
+            OpCode::Eq,
+            OpCode::NE,
+            OpCode::And,
+            OpCode::Or,
+            OpCode::CastI,
+            OpCode::CastF,
+            OpCode::CastS,
+            OpCode::Dup,
+            OpCode::Pop,
+            OpCode::Write,
+            OpCode::Halt,
+        ];
+
+        TABLE
+            .get(byte as usize)
+            .copied()
+            .ok_or_else(|| format!("ERRO: opcode desconhecido: {byte}"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinaValue {
+    Int32(i32),
+    Float32(f32),
+    Boolean(bool),
+    String(String),
+}
+
+impl LinaValue {
+    fn is_truthy(&self) -> bool {
+        matches!(self, LinaValue::Boolean(true))
+    }
+}
+
+impl std::fmt::Display for LinaValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinaValue::Int32(n) => write!(f, "{n}"),
+            LinaValue::Float32(n) => write!(f, "{n}"),
+            LinaValue::Boolean(b) => write!(f, "{b}"),
+            LinaValue::String(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// A simple stack machine that executes the bytecode produced by [`crate::compiler::Compiler`].
+pub struct Vm {
+    bytecode: Vec<u8>,
+    constants: Vec<LinaValue>,
+    stack: Vec<LinaValue>,
+    vars: Vec<LinaValue>,
+    pc: usize,
+}
+
+impl Vm {
+    pub fn new(bytecode: Vec<u8>, constants: Vec<LinaValue>) -> Self {
+        Self {
+            bytecode,
+            constants,
+            stack: Vec::new(),
+            vars: Vec::new(),
+            pc: 0,
+        }
+    }
+
+    /// The value left on top of the stack, if any. Mainly useful for tests
+    /// and tools that want to inspect the result of a standalone expression.
+    pub fn stack_top(&self) -> Option<&LinaValue> {
+        self.stack.last()
+    }
+
+    fn read_addr(&mut self) -> usize {
+        let bytes = self.bytecode[self.pc..self.pc + ADDR_SIZE]
+            .try_into()
+            .unwrap();
+        self.pc += ADDR_SIZE;
+        u64::from_le_bytes(bytes) as usize
+    }
+
+    fn read_offset(&mut self) -> isize {
+        let bytes = self.bytecode[self.pc..self.pc + OFFSET_SIZE]
+            .try_into()
+            .unwrap();
+        self.pc += OFFSET_SIZE;
+        i64::from_le_bytes(bytes) as isize
+    }
+
+    fn pop(&mut self) -> LinaValue {
+        self.stack.pop().expect("ERRO: pilha vazia")
+    }
+
+    fn push(&mut self, value: LinaValue) {
+        self.stack.push(value);
+    }
+
+    fn store(&mut self, addr: usize, value: LinaValue) {
+        if addr >= self.vars.len() {
+            self.vars.resize(addr + 1, LinaValue::Int32(0));
+        }
+        self.vars[addr] = value;
+    }
+
+    fn jump_by(&mut self, offset: isize) {
+        self.pc = (self.pc as isize + offset) as usize;
+    }
+
+    pub fn run(&mut self) {
+        loop {
+            let op = OpCode::try_from(self.bytecode[self.pc]).unwrap();
+            self.pc += 1;
+
+            match op {
+                OpCode::Const => {
+                    let addr = self.read_addr();
+                    self.push(self.constants[addr].clone());
+                }
+                OpCode::Store => {
+                    let addr = self.read_addr();
+                    let value = self.pop();
+                    self.store(addr, value);
+                }
+                OpCode::Load => {
+                    let addr = self.read_addr();
+                    self.push(self.vars[addr].clone());
+                }
+                OpCode::Jmp => {
+                    let offset = self.read_offset();
+                    self.jump_by(offset);
+                }
+                OpCode::JmpF => {
+                    let offset = self.read_offset();
+                    let cond = self.pop();
+                    if !cond.is_truthy() {
+                        self.jump_by(offset);
+                    }
+                }
+                OpCode::JmpT => {
+                    let offset = self.read_offset();
+                    let cond = self.pop();
+                    if cond.is_truthy() {
+                        self.jump_by(offset);
+                    }
+                }
+                OpCode::Add => self.binary_arith(|a, b| a + b, |a, b| a + b),
+                OpCode::Sub => self.binary_arith(|a, b| a - b, |a, b| a - b),
+                OpCode::Mul => self.binary_arith(|a, b| a * b, |a, b| a * b),
+                OpCode::Div => self.binary_arith(|a, b| a / b, |a, b| a / b),
+                OpCode::Rem => self.binary_arith(|a, b| a % b, |a, b| a % b),
+                OpCode::Pow => {
+                    let rhs = self.pop();
+                    let lhs = self.pop();
+                    self.push(pow(lhs, rhs));
+                }
+                OpCode::GT => self.compare(|o| o.is_gt()),
+                OpCode::LT => self.compare(|o| o.is_lt()),
+                OpCode::GE => self.compare(|o| o.is_ge()),
+                OpCode::LE => self.compare(|o| o.is_le()),
+                OpCode::Eq => {
+                    let rhs = self.pop();
+                    let lhs = self.pop();
+                    self.push(LinaValue::Boolean(lhs == rhs));
+                }
+                OpCode::NE => {
+                    let rhs = self.pop();
+                    let lhs = self.pop();
+                    self.push(LinaValue::Boolean(lhs != rhs));
+                }
+                OpCode::And => {
+                    let rhs = self.pop();
+                    let lhs = self.pop();
+                    self.push(LinaValue::Boolean(lhs.is_truthy() && rhs.is_truthy()));
+                }
+                OpCode::Or => {
+                    let rhs = self.pop();
+                    let lhs = self.pop();
+                    self.push(LinaValue::Boolean(lhs.is_truthy() || rhs.is_truthy()));
+                }
+                OpCode::CastI => {
+                    let value = self.pop();
+                    self.push(LinaValue::Int32(match value {
+                        LinaValue::Int32(n) => n,
+                        LinaValue::Float32(n) => n as i32,
+                        LinaValue::Boolean(b) => b as i32,
+                        LinaValue::String(s) => s
+                            .parse()
+                            .unwrap_or_else(|_| panic!("ERRO: não foi possível converter '{s}' para inteiro")),
+                    }));
+                }
+                OpCode::CastF => {
+                    let value = self.pop();
+                    self.push(LinaValue::Float32(match value {
+                        LinaValue::Int32(n) => n as f32,
+                        LinaValue::Float32(n) => n,
+                        LinaValue::Boolean(b) => b as i32 as f32,
+                        LinaValue::String(s) => s
+                            .parse()
+                            .unwrap_or_else(|_| panic!("ERRO: não foi possível converter '{s}' para real")),
+                    }));
+                }
+                OpCode::CastS => {
+                    let value = self.pop();
+                    self.push(LinaValue::String(value.to_string()));
+                }
+                OpCode::Dup => {
+                    let top = self.stack.last().expect("ERRO: pilha vazia").clone();
+                    self.push(top);
+                }
+                OpCode::Pop => {
+                    self.pop();
+                }
+                OpCode::Write => {
+                    let value = self.pop();
+                    println!("{value}");
+                }
+                OpCode::Halt => break,
+            }
+        }
+    }
+
+    fn binary_arith(&mut self, op_i: fn(i32, i32) -> i32, op_f: fn(f32, f32) -> f32) {
+        let rhs = self.pop();
+        let lhs = self.pop();
+        let result = match (lhs, rhs) {
+            (LinaValue::Int32(a), LinaValue::Int32(b)) => LinaValue::Int32(op_i(a, b)),
+            (LinaValue::Float32(a), LinaValue::Float32(b)) => LinaValue::Float32(op_f(a, b)),
+            (LinaValue::Int32(a), LinaValue::Float32(b)) => LinaValue::Float32(op_f(a as f32, b)),
+            (LinaValue::Float32(a), LinaValue::Int32(b)) => LinaValue::Float32(op_f(a, b as f32)),
+            _ => panic!("ERRO: operação aritmética inválida entre os tipos informados"),
+        };
+        self.push(result);
+    }
+
+    fn compare(&mut self, accept: fn(std::cmp::Ordering) -> bool) {
+        let rhs = self.pop();
+        let lhs = self.pop();
+        let ordering = match (lhs, rhs) {
+            (LinaValue::Int32(a), LinaValue::Int32(b)) => a.cmp(&b),
+            (LinaValue::Float32(a), LinaValue::Float32(b)) => {
+                a.partial_cmp(&b).expect("ERRO: comparação inválida entre NaN")
+            }
+            (LinaValue::Int32(a), LinaValue::Float32(b)) => (a as f32)
+                .partial_cmp(&b)
+                .expect("ERRO: comparação inválida entre NaN"),
+            (LinaValue::Float32(a), LinaValue::Int32(b)) => a
+                .partial_cmp(&(b as f32))
+                .expect("ERRO: comparação inválida entre NaN"),
+            _ => panic!("ERRO: comparação inválida entre os tipos informados"),
+        };
+        self.push(LinaValue::Boolean(accept(ordering)));
+    }
+}
+
+/// Integer exponentiation for `Int32`/`Int32` operands; a negative exponent
+/// promotes to `Float32` instead of truncating to zero. `f32::powf` is used
+/// whenever either operand is already `Float32`.
+fn pow(lhs: LinaValue, rhs: LinaValue) -> LinaValue {
+    match (lhs, rhs) {
+        (LinaValue::Int32(base), LinaValue::Int32(exp)) if exp >= 0 => {
+            LinaValue::Int32(base.pow(exp as u32))
+        }
+        (LinaValue::Int32(base), LinaValue::Int32(exp)) => {
+            LinaValue::Float32((base as f32).powf(exp as f32))
+        }
+        (LinaValue::Float32(base), LinaValue::Int32(exp)) => LinaValue::Float32(base.powf(exp as f32)),
+        (LinaValue::Int32(base), LinaValue::Float32(exp)) => LinaValue::Float32((base as f32).powf(exp)),
+        (LinaValue::Float32(base), LinaValue::Float32(exp)) => LinaValue::Float32(base.powf(exp)),
+        _ => panic!("ERRO: operador '^' requer operandos numéricos"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pow_int_with_nonnegative_exponent_stays_int() {
+        assert_eq!(pow(LinaValue::Int32(2), LinaValue::Int32(10)), LinaValue::Int32(1024));
+    }
+
+    #[test]
+    fn pow_int_with_negative_exponent_promotes_to_float() {
+        assert_eq!(pow(LinaValue::Int32(2), LinaValue::Int32(-1)), LinaValue::Float32(0.5));
+    }
+
+    #[test]
+    fn pow_float_operand_uses_powf() {
+        assert_eq!(pow(LinaValue::Float32(2.0), LinaValue::Float32(0.5)), LinaValue::Float32(2.0f32.powf(0.5)));
+    }
+}